@@ -1,9 +1,139 @@
+// 默认启用 `std`;关闭该 feature 并启用 `libm-backend`(它引入可选依赖 `libm`)时走 `no_std` + `libm`,
+// 以便在嵌入式目标上使用核心转换逻辑。只关闭 `std` 而不启用 `libm-backend` 无法编译。
+// `cfg(test)` 始终保留 `std`,否则测试模块里的 `println!`/`proptest` 在 no_std 构建下无法编译。
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+// 统一的超越函数入口: 有 `std` 时走标准库,否则经由 `libm` 提供同样的 sin/cos/sqrt/atan2 等实现,
+// 这样 gcj02_bd09/gcj02_wgs84/mercator/geodesic 等核心模块可以在两种后端下共享同一套数值代码。
+mod math {
+    #[cfg(feature = "std")]
+    pub fn sin(x: f64) -> f64 {
+        x.sin()
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn sin(x: f64) -> f64 {
+        libm::sin(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn cos(x: f64) -> f64 {
+        x.cos()
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn cos(x: f64) -> f64 {
+        libm::cos(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn sin_cos(x: f64) -> (f64, f64) {
+        x.sin_cos()
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn sin_cos(x: f64) -> (f64, f64) {
+        (libm::sin(x), libm::cos(x))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn sqrt(x: f64) -> f64 {
+        x.sqrt()
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn sqrt(x: f64) -> f64 {
+        libm::sqrt(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn atan2(y: f64, x: f64) -> f64 {
+        y.atan2(x)
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn atan2(y: f64, x: f64) -> f64 {
+        libm::atan2(y, x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn atan(x: f64) -> f64 {
+        x.atan()
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn atan(x: f64) -> f64 {
+        libm::atan(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn tan(x: f64) -> f64 {
+        x.tan()
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn tan(x: f64) -> f64 {
+        libm::tan(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn ln(x: f64) -> f64 {
+        x.ln()
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn ln(x: f64) -> f64 {
+        libm::log(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn exp(x: f64) -> f64 {
+        x.exp()
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn exp(x: f64) -> f64 {
+        libm::exp(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn powi(x: f64, n: i32) -> f64 {
+        x.powi(n)
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn powi(x: f64, n: i32) -> f64 {
+        libm::pow(x, n as f64)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn abs(x: f64) -> f64 {
+        x.abs()
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn abs(x: f64) -> f64 {
+        libm::fabs(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn round(x: f64) -> f64 {
+        x.round()
+    }
+    #[cfg(not(feature = "std"))]
+    pub fn round(x: f64) -> f64 {
+        libm::round(x)
+    }
+}
+
 // 定义坐标系类型
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CoordSystem {
     WGS84,
     GCJ02,
     BD09,
+    // 投影坐标系(米),标准 Web 墨卡托,EPSG:3857
+    WebMercator,
+    // 百度地图米制坐标系,基于 BD09 的分段多项式拟合投影(见 mercator::bd09_to_bd09mc)
+    BD09MC,
+    // 任意自定义基准面(如北京 54、西安 80 或自定义 CORS 框架): 携带椭球参数 +
+    // 到 WGS84 的布尔莎七参数,即可经由 transform_datum 与 GCJ02/BD09 等既有步骤组合使用
+    Custom(CustomDatum),
 }
 
 // 定义坐标结构体
@@ -18,12 +148,278 @@ pub struct Coordinate {
 pub enum ConvertError {
     UnsupportedConversion,
     OutOfChina,
+    // 解析坐标字符串失败,或解析出的经纬度超出合法范围
+    ParseError,
+    // 经纬度超出合法范围(|lat| > 90 或 |lng| > 180)
+    OutOfRange,
+}
+
+// 紧凑定点坐标: 经纬度各按 ×1e7 缩放存成 i32(约 1cm 分辨率),供批量坐标数组降低内存占用,
+// 同时相比 f64 具备精确的相等语义。lng 或 lat 等于 INVALID 时代表"无效坐标"哨兵值。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedCoordinate {
+    pub lng: i32,
+    pub lat: i32,
+}
+
+impl FixedCoordinate {
+    const SCALE: f64 = 1e7;
+    pub const INVALID: i32 = i32::MIN;
+
+    pub fn is_valid(self) -> bool {
+        self.lng != Self::INVALID && self.lat != Self::INVALID
+    }
 }
 
 impl Coordinate {
     pub fn new(lng: f64, lat: f64) -> Self {
         Self { lng, lat }
     }
+
+    // 校验经纬度范围后再构造,拒绝 |lat| > 90 或 |lng| > 180 的非法输入
+    pub fn try_new(lng: f64, lat: f64) -> Result<Self, ConvertError> {
+        if lat.abs() > 90.0 || lng.abs() > 180.0 {
+            return Err(ConvertError::OutOfRange);
+        }
+
+        Ok(Self::new(lng, lat))
+    }
+
+    // 校验范围后再缩放,避免越界坐标(如 |lng| > 180)缩放后饱和为 i32::MIN,
+    // 与 FixedCoordinate::INVALID 哨兵值撞车,导致 from_fixed 把越界坐标误报为"无效坐标"
+    pub fn to_fixed(self) -> Result<FixedCoordinate, ConvertError> {
+        if self.lat.abs() > 90.0 || self.lng.abs() > 180.0 {
+            return Err(ConvertError::OutOfRange);
+        }
+
+        Ok(FixedCoordinate {
+            lng: math::round(self.lng * FixedCoordinate::SCALE) as i32,
+            lat: math::round(self.lat * FixedCoordinate::SCALE) as i32,
+        })
+    }
+
+    pub fn from_fixed(fixed: FixedCoordinate) -> Option<Self> {
+        if !fixed.is_valid() {
+            return None;
+        }
+
+        Some(Self::new(
+            fixed.lng as f64 / FixedCoordinate::SCALE,
+            fixed.lat as f64 / FixedCoordinate::SCALE,
+        ))
+    }
+
+    // 从当前点出发,沿给定方位角(度)行进指定距离(米),返回到达的坐标(归一化到 WGS84 再解算)
+    pub fn destination(
+        self,
+        bearing_deg: f64,
+        distance_m: f64,
+        system: CoordSystem,
+    ) -> Result<Coordinate, ConvertError> {
+        let wgs84 = transform(self, system, CoordSystem::WGS84)?;
+        let dest_wgs84 = geodesic::vincenty_direct(wgs84, bearing_deg, distance_m);
+
+        transform(dest_wgs84, CoordSystem::WGS84, system)
+    }
+}
+
+// 支持解析十进制("39.9042, 116.4074")和度分秒("39°54′15″N 116°24′26″E")两种常见格式,
+// 解析出的坐标默认视为 WGS84,范围校验失败或格式不识别均返回 ConvertError::ParseError。
+impl core::str::FromStr for Coordinate {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let (lat, lng) = if s.contains(',') {
+            parse_decimal(s)?
+        } else {
+            parse_dms(s)?
+        };
+
+        if lat.abs() > 90.0 || lng.abs() > 180.0 {
+            return Err(ConvertError::ParseError);
+        }
+
+        Ok(Coordinate::new(lng, lat))
+    }
+}
+
+fn parse_decimal(s: &str) -> Result<(f64, f64), ConvertError> {
+    let mut parts = s.split(',').map(str::trim);
+    let lat = parts.next().ok_or(ConvertError::ParseError)?;
+    let lng = parts.next().ok_or(ConvertError::ParseError)?;
+    if parts.next().is_some() {
+        return Err(ConvertError::ParseError);
+    }
+
+    let lat: f64 = lat.parse().map_err(|_| ConvertError::ParseError)?;
+    let lng: f64 = lng.parse().map_err(|_| ConvertError::ParseError)?;
+
+    Ok((lat, lng))
+}
+
+fn parse_dms(s: &str) -> Result<(f64, f64), ConvertError> {
+    let normalized = s.replace(['°', '′', '″', '\'', '"'], " ");
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    if tokens.len() != 8 {
+        return Err(ConvertError::ParseError);
+    }
+
+    let lat = dms_to_decimal(tokens[0], tokens[1], tokens[2], tokens[3], 'N', 'S')?;
+    let lng = dms_to_decimal(tokens[4], tokens[5], tokens[6], tokens[7], 'E', 'W')?;
+
+    Ok((lat, lng))
+}
+
+fn dms_to_decimal(
+    deg: &str,
+    min: &str,
+    sec: &str,
+    hemisphere: &str,
+    positive: char,
+    negative: char,
+) -> Result<f64, ConvertError> {
+    let deg: f64 = deg.parse().map_err(|_| ConvertError::ParseError)?;
+    let min: f64 = min.parse().map_err(|_| ConvertError::ParseError)?;
+    let sec: f64 = sec.parse().map_err(|_| ConvertError::ParseError)?;
+
+    let hemisphere = hemisphere
+        .chars()
+        .next()
+        .ok_or(ConvertError::ParseError)?
+        .to_ascii_uppercase();
+
+    let value = deg + min / 60.0 + sec / 3600.0;
+    if hemisphere == positive {
+        Ok(value)
+    } else if hemisphere == negative {
+        Ok(-value)
+    } else {
+        Err(ConvertError::ParseError)
+    }
+}
+
+// 椭球参数(长半轴 a,扁率 f),用于描述任意大地基准面
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ellipsoid {
+    pub a: f64,
+    pub f: f64,
+}
+
+impl Ellipsoid {
+    pub const WGS84: Ellipsoid = Ellipsoid {
+        a: 6378137.0,
+        f: 1.0 / 298.257223563,
+    };
+}
+
+// 布尔莎七参数(Helmert)转换: X' = T + (1+s)·R·X,旋转角以角秒为单位,采用小角度近似
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Helmert {
+    pub dx: f64,
+    pub dy: f64,
+    pub dz: f64,
+    pub rx: f64,
+    pub ry: f64,
+    pub rz: f64,
+    pub scale_ppm: f64,
+}
+
+impl Helmert {
+    const ARCSEC_TO_RAD: f64 = 4.848e-6;
+
+    pub fn apply(&self, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        let rx = self.rx * Self::ARCSEC_TO_RAD;
+        let ry = self.ry * Self::ARCSEC_TO_RAD;
+        let rz = self.rz * Self::ARCSEC_TO_RAD;
+        let scale = 1.0 + self.scale_ppm * 1e-6;
+
+        let x2 = scale * (x - rz * y + ry * z) + self.dx;
+        let y2 = scale * (rz * x + y - rx * z) + self.dy;
+        let z2 = scale * (-ry * x + rx * y + z) + self.dz;
+
+        (x2, y2, z2)
+    }
+
+    // 小角度近似下,七参数变换的逆约等于所有参数取负;与 apply 共用的小角度假设
+    // 精度相当,不引入额外误差
+    pub fn inverse(&self) -> Helmert {
+        Helmert {
+            dx: -self.dx,
+            dy: -self.dy,
+            dz: -self.dz,
+            rx: -self.rx,
+            ry: -self.ry,
+            rz: -self.rz,
+            scale_ppm: -self.scale_ppm,
+        }
+    }
+}
+
+// 描述一个自定义基准面,足以通过 transform_datum 经由 ECEF 与 WGS84 互转:
+// 椭球参数定义该基准面本身,to_wgs84 是从该基准面转到 WGS84 的布尔莎七参数。
+// 装进 CoordSystem::Custom 后即可像 GCJ02/BD09 一样传给 transform。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CustomDatum {
+    pub ellipsoid: Ellipsoid,
+    pub to_wgs84: Helmert,
+}
+
+// 大地坐标(经纬度 + 大地高)转换为地心地固坐标系(ECEF)
+pub fn geodetic_to_ecef(coord: Coordinate, height: f64, ellipsoid: Ellipsoid) -> (f64, f64, f64) {
+    let e_sq = ellipsoid.f * (2.0 - ellipsoid.f);
+    let rad_lat = coord.lat.to_radians();
+    let rad_lng = coord.lng.to_radians();
+
+    let n = ellipsoid.a / math::sqrt(1.0 - e_sq * math::powi(math::sin(rad_lat), 2));
+
+    let x = (n + height) * math::cos(rad_lat) * math::cos(rad_lng);
+    let y = (n + height) * math::cos(rad_lat) * math::sin(rad_lng);
+    let z = (n * (1.0 - e_sq) + height) * math::sin(rad_lat);
+
+    (x, y, z)
+}
+
+// ECEF 坐标转换回大地坐标(经纬度 + 大地高),纬度通过迭代求解
+pub fn ecef_to_geodetic(x: f64, y: f64, z: f64, ellipsoid: Ellipsoid) -> (Coordinate, f64) {
+    let e_sq = ellipsoid.f * (2.0 - ellipsoid.f);
+    let p = math::sqrt(x * x + y * y);
+    let lng = math::atan2(y, x);
+
+    let mut lat = math::atan2(z, p * (1.0 - e_sq));
+
+    for _ in 0..10 {
+        let n = ellipsoid.a / math::sqrt(1.0 - e_sq * math::powi(math::sin(lat), 2));
+        let height = p / math::cos(lat) - n;
+        let new_lat = math::atan2(z, p * (1.0 - e_sq * n / (n + height)));
+        if math::abs(new_lat - lat) < 1e-12 {
+            lat = new_lat;
+            break;
+        }
+        lat = new_lat;
+    }
+
+    let n = ellipsoid.a / math::sqrt(1.0 - e_sq * math::powi(math::sin(lat), 2));
+    let height = p / math::cos(lat) - n;
+
+    (Coordinate::new(lng.to_degrees(), lat.to_degrees()), height)
+}
+
+// 经由 ECEF 的通用基准面转换: 在源椭球上展开为 ECEF,施加布尔莎七参数,再投影回目标椭球。
+// 可用于接入任意自定义基准面(如北京 54、西安 80 或自定义 CORS 框架),与现有的
+// GCJ02/BD09 转换步骤配合使用 —— 见 CoordSystem::Custom 与 transform。
+pub fn transform_datum(
+    coord: Coordinate,
+    height: f64,
+    from: Ellipsoid,
+    to: Ellipsoid,
+    helmert: Helmert,
+) -> Coordinate {
+    let (x, y, z) = geodetic_to_ecef(coord, height, from);
+    let (x2, y2, z2) = helmert.apply(x, y, z);
+
+    ecef_to_geodetic(x2, y2, z2, to).0
 }
 
 // 核心转换实现
@@ -32,33 +428,242 @@ pub fn transform(
     from: CoordSystem,
     to: CoordSystem,
 ) -> Result<Coordinate, ConvertError> {
+    if let Some(result) = transform_custom_datum(coord, from, to) {
+        return result;
+    }
+
+    resolve_transform(from, to)(coord)
+}
+
+// CoordSystem::Custom 携带运行时数据,无法像其余分支那样直接转成 fn 指针塞进
+// resolve_transform 的分派表,因此单独在这里处理: 以 WGS84 为公共枢纽,借助
+// transform_datum 与自定义基准面互转,再递归交给 transform 走剩下的标准路径。
+// 返回 None 表示 from/to 都不是 Custom,调用方应改走 resolve_transform。
+fn transform_custom_datum(
+    coord: Coordinate,
+    from: CoordSystem,
+    to: CoordSystem,
+) -> Option<Result<Coordinate, ConvertError>> {
+    if from == to {
+        if let CoordSystem::Custom(_) = from {
+            return Some(Ok(coord));
+        }
+    }
+
+    match (from, to) {
+        (CoordSystem::Custom(datum), CoordSystem::WGS84) => {
+            Some(Ok(custom_to_wgs84(coord, datum)))
+        }
+        (CoordSystem::WGS84, CoordSystem::Custom(datum)) => {
+            Some(Ok(wgs84_to_custom(coord, datum)))
+        }
+        (CoordSystem::Custom(datum), to) => {
+            Some(transform(custom_to_wgs84(coord, datum), CoordSystem::WGS84, to))
+        }
+        (from, CoordSystem::Custom(datum)) => {
+            Some(transform(coord, from, CoordSystem::WGS84).map(|wgs84| wgs84_to_custom(wgs84, datum)))
+        }
+        _ => None,
+    }
+}
+
+fn custom_to_wgs84(coord: Coordinate, datum: CustomDatum) -> Coordinate {
+    transform_datum(coord, 0.0, datum.ellipsoid, Ellipsoid::WGS84, datum.to_wgs84)
+}
+
+fn wgs84_to_custom(coord: Coordinate, datum: CustomDatum) -> Coordinate {
+    transform_datum(
+        coord,
+        0.0,
+        Ellipsoid::WGS84,
+        datum.ellipsoid,
+        datum.to_wgs84.inverse(),
+    )
+}
+
+// 根据 (from, to) 解析出对应的转换函数,供 transform 以及批量接口共用:
+// 所有分支都是不捕获环境的函数项,直接强转成函数指针即可,避免为单点转换这个
+// 高频路径引入不必要的堆分配和动态派发;批量接口则借此只需在处理整个缓冲区前
+// 解析一次,避免对每个元素都重新 match (from, to)。
+fn resolve_transform(
+    from: CoordSystem,
+    to: CoordSystem,
+) -> fn(Coordinate) -> Result<Coordinate, ConvertError> {
     match (from, to) {
-        (CoordSystem::WGS84, CoordSystem::GCJ02) => gcj02_wgs84::wgs84_to_gcj02(coord),
-        (CoordSystem::GCJ02, CoordSystem::WGS84) => gcj02_wgs84::gcj02_to_wgs84(coord),
-        (CoordSystem::GCJ02, CoordSystem::BD09) => Ok(gcj02_bd09::gcj02_to_bd09(coord)),
-        (CoordSystem::BD09, CoordSystem::GCJ02) => Ok(gcj02_bd09::bd09_to_gcj02(coord)),
-        (CoordSystem::WGS84, CoordSystem::BD09) => Ok(gcj02_bd09::gcj02_to_bd09(
-            gcj02_wgs84::wgs84_to_gcj02(coord)?,
-        )),
-        (CoordSystem::BD09, CoordSystem::WGS84) => Ok(gcj02_wgs84::gcj02_to_wgs84(
-            gcj02_bd09::bd09_to_gcj02(coord),
-        ))?,
-        _ => Ok(coord), // 相同坐标系直接返回
+        (CoordSystem::WGS84, CoordSystem::GCJ02) => gcj02_wgs84::wgs84_to_gcj02,
+        (CoordSystem::GCJ02, CoordSystem::WGS84) => gcj02_wgs84::gcj02_to_wgs84,
+        (CoordSystem::GCJ02, CoordSystem::BD09) => |c| Ok(gcj02_bd09::gcj02_to_bd09(c)),
+        (CoordSystem::BD09, CoordSystem::GCJ02) => |c| Ok(gcj02_bd09::bd09_to_gcj02(c)),
+        (CoordSystem::WGS84, CoordSystem::BD09) => {
+            |c| Ok(gcj02_bd09::gcj02_to_bd09(gcj02_wgs84::wgs84_to_gcj02(c)?))
+        }
+        (CoordSystem::BD09, CoordSystem::WGS84) => {
+            |c| gcj02_wgs84::gcj02_to_wgs84(gcj02_bd09::bd09_to_gcj02(c))
+        }
+
+        (CoordSystem::WGS84, CoordSystem::WebMercator) => {
+            |c| Ok(mercator::wgs84_to_web_mercator(c))
+        }
+        (CoordSystem::WebMercator, CoordSystem::WGS84) => {
+            |c| Ok(mercator::web_mercator_to_wgs84(c))
+        }
+        (CoordSystem::GCJ02, CoordSystem::WebMercator) => {
+            |c| Ok(mercator::wgs84_to_web_mercator(gcj02_wgs84::gcj02_to_wgs84(c)?))
+        }
+        (CoordSystem::WebMercator, CoordSystem::GCJ02) => {
+            |c| gcj02_wgs84::wgs84_to_gcj02(mercator::web_mercator_to_wgs84(c))
+        }
+        (CoordSystem::BD09, CoordSystem::WebMercator) => |c| {
+            Ok(mercator::wgs84_to_web_mercator(gcj02_wgs84::gcj02_to_wgs84(
+                gcj02_bd09::bd09_to_gcj02(c),
+            )?))
+        },
+        (CoordSystem::WebMercator, CoordSystem::BD09) => |c| {
+            Ok(gcj02_bd09::gcj02_to_bd09(
+                gcj02_wgs84::wgs84_to_gcj02(mercator::web_mercator_to_wgs84(c))?,
+            ))
+        },
+
+        (CoordSystem::BD09, CoordSystem::BD09MC) => |c| Ok(mercator::bd09_to_bd09mc(c)),
+        (CoordSystem::BD09MC, CoordSystem::BD09) => |c| Ok(mercator::bd09mc_to_bd09(c)),
+        (CoordSystem::WGS84, CoordSystem::BD09MC) => |c| {
+            Ok(mercator::bd09_to_bd09mc(gcj02_bd09::gcj02_to_bd09(
+                gcj02_wgs84::wgs84_to_gcj02(c)?,
+            )))
+        },
+        (CoordSystem::BD09MC, CoordSystem::WGS84) => |c| {
+            gcj02_wgs84::gcj02_to_wgs84(gcj02_bd09::bd09_to_gcj02(mercator::bd09mc_to_bd09(c)))
+        },
+        (CoordSystem::GCJ02, CoordSystem::BD09MC) => {
+            |c| Ok(mercator::bd09_to_bd09mc(gcj02_bd09::gcj02_to_bd09(c)))
+        }
+        (CoordSystem::BD09MC, CoordSystem::GCJ02) => {
+            |c| Ok(gcj02_bd09::bd09_to_gcj02(mercator::bd09mc_to_bd09(c)))
+        }
+        (CoordSystem::WebMercator, CoordSystem::BD09MC) => |c| {
+            let wgs84 = mercator::web_mercator_to_wgs84(c);
+            let bd09 = gcj02_bd09::gcj02_to_bd09(gcj02_wgs84::wgs84_to_gcj02(wgs84)?);
+            Ok(mercator::bd09_to_bd09mc(bd09))
+        },
+        (CoordSystem::BD09MC, CoordSystem::WebMercator) => |c| {
+            let bd09 = mercator::bd09mc_to_bd09(c);
+            let wgs84 = gcj02_wgs84::gcj02_to_wgs84(gcj02_bd09::bd09_to_gcj02(bd09))?;
+            Ok(mercator::wgs84_to_web_mercator(wgs84))
+        },
+
+        // 相同坐标系直接返回; CoordSystem::Custom 由 transform 里的 transform_custom_datum
+        // 提前拦截处理,不会走到这里
+        _ => Ok,
+    }
+}
+
+// 计算两点间的大地线距离(米),先归一化到 WGS84,再用 Vincenty 逆解公式求解
+pub fn distance(a: Coordinate, b: Coordinate, system: CoordSystem) -> Result<f64, ConvertError> {
+    let a_wgs84 = transform(a, system, CoordSystem::WGS84)?;
+    let b_wgs84 = transform(b, system, CoordSystem::WGS84)?;
+
+    Ok(geodesic::vincenty_inverse(a_wgs84, b_wgs84))
+}
+
+// 与 transform(coord, GCJ02, WGS84) 等价,但允许调用方配置迭代求解的最大步数上限,
+// 避免在精度受限的后端(例如 no_std + libm)下反复迭代而不收敛
+pub fn gcj02_to_wgs84_with_max_iterations(
+    coord: Coordinate,
+    max_iterations: u32,
+) -> Result<Coordinate, ConvertError> {
+    gcj02_wgs84::gcj02_to_wgs84_with_max_iterations(coord, max_iterations)
+}
+
+// 对一批坐标应用 transform,转换函数只通过 resolve_transform 解析一次(from/to 只 match 一次),
+// 随后套用到整个切片上,返回每个元素各自的转换结果。
+// CoordSystem::Custom 携带运行时数据,resolve_transform 的 fn 指针分派表覆盖不到,
+// 这种情况退化为对每个元素调用一次 transform。
+pub fn transform_slice(
+    coords: &[Coordinate],
+    from: CoordSystem,
+    to: CoordSystem,
+) -> Vec<Result<Coordinate, ConvertError>> {
+    if uses_custom_datum(from, to) {
+        return coords.iter().map(|&coord| transform(coord, from, to)).collect();
+    }
+
+    let convert = resolve_transform(from, to);
+    coords.iter().map(|&coord| convert(coord)).collect()
+}
+
+// 原地批量转换: 转换函数只通过 resolve_transform 解析一次,随后套用到整个缓冲区上
+// (CoordSystem::Custom 同样退化为逐元素调用 transform,原因同 transform_slice)。
+// 遇到第一个转换失败就中止,已写入的元素保持为转换后的值;返回的错误带上失败元素的下标,
+// 让调用方能判断缓冲区里 0..index 已经转换、index.. 仍是原值,而不是只能假定"安全"。
+pub fn transform_slice_mut(
+    coords: &mut [Coordinate],
+    from: CoordSystem,
+    to: CoordSystem,
+) -> Result<(), (usize, ConvertError)> {
+    if uses_custom_datum(from, to) {
+        for (index, coord) in coords.iter_mut().enumerate() {
+            *coord = transform(*coord, from, to).map_err(|err| (index, err))?;
+        }
+        return Ok(());
+    }
+
+    let convert = resolve_transform(from, to);
+
+    for (index, coord) in coords.iter_mut().enumerate() {
+        *coord = convert(*coord).map_err(|err| (index, err))?;
+    }
+
+    Ok(())
+}
+
+fn uses_custom_datum(from: CoordSystem, to: CoordSystem) -> bool {
+    matches!(from, CoordSystem::Custom(_)) || matches!(to, CoordSystem::Custom(_))
+}
+
+// GeoJSON 风格的几何体,用于一次性对整张图形做坐标系转换
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    Point(Coordinate),
+    LineString(Vec<Coordinate>),
+    // 外环 + 可能的内环(洞),与 GeoJSON Polygon 的 rings 语义一致
+    Polygon(Vec<Vec<Coordinate>>),
+}
+
+// 递归地对几何体内的每个坐标做转换,遇到任意坐标转换失败就返回该错误
+pub fn transform_geometry(
+    geometry: &Geometry,
+    from: CoordSystem,
+    to: CoordSystem,
+) -> Result<Geometry, ConvertError> {
+    match geometry {
+        Geometry::Point(coord) => Ok(Geometry::Point(transform(*coord, from, to)?)),
+        Geometry::LineString(coords) => {
+            let mut coords = coords.clone();
+            transform_slice_mut(&mut coords, from, to).map_err(|(_, err)| err)?;
+            Ok(Geometry::LineString(coords))
+        }
+        Geometry::Polygon(rings) => {
+            let mut rings = rings.clone();
+            for ring in rings.iter_mut() {
+                transform_slice_mut(ring, from, to).map_err(|(_, err)| err)?;
+            }
+            Ok(Geometry::Polygon(rings))
+        }
     }
 }
 
 mod gcj02_bd09 {
-    use super::Coordinate;
+    use super::{math, Coordinate};
 
-    const BAIDU_FACTOR: f64 = std::f64::consts::PI * 3000.0 / 180.0;
+    const BAIDU_FACTOR: f64 = core::f64::consts::PI * 3000.0 / 180.0;
     pub fn bd09_to_gcj02(coord: Coordinate) -> Coordinate {
         let x = coord.lng - 0.0065;
         let y = coord.lat - 0.006;
-        let z = (x.powi(2) + y.powi(2)).sqrt() - 0.00002 * (y * BAIDU_FACTOR).sin();
-        let theta = y.atan2(x) - 0.000003 * (x * BAIDU_FACTOR).cos();
+        let z = math::sqrt(math::powi(x, 2) + math::powi(y, 2)) - 0.00002 * math::sin(y * BAIDU_FACTOR);
+        let theta = math::atan2(y, x) - 0.000003 * math::cos(x * BAIDU_FACTOR);
 
-        let lng = z * theta.cos();
-        let lat = z * theta.sin();
+        let lng = z * math::cos(theta);
+        let lat = z * math::sin(theta);
 
         Coordinate::new(lng, lat)
     }
@@ -66,41 +671,46 @@ mod gcj02_bd09 {
     pub fn gcj02_to_bd09(coord: Coordinate) -> Coordinate {
         let x = coord.lng;
         let y = coord.lat;
-        let z = (x.powi(2) + y.powi(2)).sqrt() + 0.00002 * (y * BAIDU_FACTOR).sin();
-        let theta = y.atan2(x) + 0.000003 * (x * BAIDU_FACTOR).cos();
+        let z = math::sqrt(math::powi(x, 2) + math::powi(y, 2)) + 0.00002 * math::sin(y * BAIDU_FACTOR);
+        let theta = math::atan2(y, x) + 0.000003 * math::cos(x * BAIDU_FACTOR);
 
-        let lng: f64 = z * theta.cos() + 0.0065;
-        let lat = z * theta.sin() + 0.006;
+        let lng: f64 = z * math::cos(theta) + 0.0065;
+        let lat = z * math::sin(theta) + 0.006;
 
         Coordinate::new(lng, lat)
     }
 }
 
 mod gcj02_wgs84 {
-    use super::{ConvertError, Coordinate};
+    use super::{math, ConvertError, Coordinate};
 
     const A: f64 = 6378245.0;
     const EE: f64 = 0.006693421622965823;
-    const PI: f64 = std::f64::consts::PI;
+    const PI: f64 = core::f64::consts::PI;
+
+    // gcj02_to_wgs84 迭代求解的默认最大迭代次数,防止在精度受限的后端(如 no_std + libm)下空转
+    const DEFAULT_MAX_ITERATIONS: u32 = 10;
 
     // 检查坐标是否在中国范围内
     fn is_in_china_bbox(lon: f64, lat: f64) -> bool {
-        lon >= 72.004 && lon <= 137.8347 && lat >= 0.8293 && lat <= 55.8271
+        (72.004..=137.8347).contains(&lon) && (0.8293..=55.8271).contains(&lat)
     }
 
     fn transform_lat(x: f64, y: f64) -> f64 {
-        let mut ret = -100.0 + 2.0 * x + 3.0 * y + 0.2 * y * y + 0.1 * x * y + 0.2 * x.abs().sqrt();
-        ret += ((20.0 * (6f64 * x * PI).sin() + 20.0 * (2.0 * x * PI).sin()) * 2.0) / 3.0;
-        ret += ((20.0 * (y * PI).sin() + 40.0 * (y / 3.0 * PI).sin()) * 2.0) / 3.0;
-        ret += ((160.0 * (y / 12.0 * PI).sin() + 320.0 * (y * PI / 30.0).sin()) * 2.0) / 3.0;
+        let mut ret =
+            -100.0 + 2.0 * x + 3.0 * y + 0.2 * y * y + 0.1 * x * y + 0.2 * math::sqrt(math::abs(x));
+        ret += ((20.0 * math::sin(6f64 * x * PI) + 20.0 * math::sin(2.0 * x * PI)) * 2.0) / 3.0;
+        ret += ((20.0 * math::sin(y * PI) + 40.0 * math::sin(y / 3.0 * PI)) * 2.0) / 3.0;
+        ret += ((160.0 * math::sin(y / 12.0 * PI) + 320.0 * math::sin(y * PI / 30.0)) * 2.0) / 3.0;
         ret
     }
 
     fn transform_lon(x: f64, y: f64) -> f64 {
-        let mut ret = 300.0 + x + 2.0 * y + 0.1 * x * x + 0.1 * x * y + 0.1 * x.abs().sqrt();
-        ret += ((20.0 * (6f64 * x * PI).sin() + 20.0 * (2.0 * x * PI).sin()) * 2.0) / 3.0;
-        ret += ((20.0 * (x * PI).sin() + 40.0 * (x / 3.0 * PI).sin()) * 2.0) / 3.0;
-        ret += ((150.0 * (x / 12.0 * PI).sin() + 300.0 * (x / 30.0 * PI).sin()) * 2.0) / 3.0;
+        let mut ret =
+            300.0 + x + 2.0 * y + 0.1 * x * x + 0.1 * x * y + 0.1 * math::sqrt(math::abs(x));
+        ret += ((20.0 * math::sin(6f64 * x * PI) + 20.0 * math::sin(2.0 * x * PI)) * 2.0) / 3.0;
+        ret += ((20.0 * math::sin(x * PI) + 40.0 * math::sin(x / 3.0 * PI)) * 2.0) / 3.0;
+        ret += ((150.0 * math::sin(x / 12.0 * PI) + 300.0 * math::sin(x / 30.0 * PI)) * 2.0) / 3.0;
         ret
     }
 
@@ -109,12 +719,12 @@ mod gcj02_wgs84 {
         let d_lat = transform_lat(lon - 105.0, lat - 35.0);
 
         let rad_lat = lat / 180.0 * PI;
-        let magic = rad_lat.sin();
+        let magic = math::sin(rad_lat);
 
         let magic = 1.0 - EE * magic * magic;
-        let sqrt_magic = magic.sqrt();
+        let sqrt_magic = math::sqrt(magic);
 
-        let d_lon = (d_lon * 180.0) / ((A / sqrt_magic) * rad_lat.cos() * PI);
+        let d_lon = (d_lon * 180.0) / ((A / sqrt_magic) * math::cos(rad_lat) * PI);
         let d_lat = (d_lat * 180.0) / (((A * (1.0 - EE)) / (magic * sqrt_magic)) * PI);
 
         (d_lon, d_lat)
@@ -133,6 +743,14 @@ mod gcj02_wgs84 {
     }
 
     pub fn gcj02_to_wgs84(coord: Coordinate) -> Result<Coordinate, ConvertError> {
+        gcj02_to_wgs84_with_max_iterations(coord, DEFAULT_MAX_ITERATIONS)
+    }
+
+    // 与 gcj02_to_wgs84 等价,但允许调用方配置最大迭代次数上限
+    pub fn gcj02_to_wgs84_with_max_iterations(
+        coord: Coordinate,
+        max_iterations: u32,
+    ) -> Result<Coordinate, ConvertError> {
         let (lon, lat) = (coord.lng, coord.lat);
 
         if !is_in_china_bbox(lon, lat) {
@@ -142,16 +760,12 @@ mod gcj02_wgs84 {
         let mut wgs_lon = lon;
         let mut wgs_lat = lat;
 
-        loop {
-            let temp_point = wgs84_to_gcj02(Coordinate::new(wgs_lon, wgs_lat));
-            if temp_point.is_err() {
-                return temp_point;
-            }
-            let temp_point = temp_point.unwrap();
+        for _ in 0..max_iterations {
+            let temp_point = wgs84_to_gcj02(Coordinate::new(wgs_lon, wgs_lat))?;
             let dx = temp_point.lng - lon;
             let dy = temp_point.lat - lat;
 
-            if dx.abs() < 1e-6 && dy.abs() < 1e-6 {
+            if math::abs(dx) < 1e-6 && math::abs(dy) < 1e-6 {
                 break;
             }
 
@@ -163,6 +777,388 @@ mod gcj02_wgs84 {
     }
 }
 
+mod geodesic {
+    use super::{math, Coordinate};
+
+    // WGS84 椭球参数
+    const A: f64 = 6378137.0;
+    const F: f64 = 1.0 / 298.257223563;
+    const B: f64 = A * (1.0 - F);
+    const MAX_ITERATIONS: u32 = 200;
+    const CONVERGENCE_THRESHOLD: f64 = 1e-12;
+
+    // Vincenty 逆解: 已知两点经纬度求大地线距离(米)
+    pub fn vincenty_inverse(p1: Coordinate, p2: Coordinate) -> f64 {
+        if math::abs(p1.lng - p2.lng) < f64::EPSILON && math::abs(p1.lat - p2.lat) < f64::EPSILON {
+            return 0.0;
+        }
+
+        let u1 = math::atan((1.0 - F) * math::tan(p1.lat.to_radians()));
+        let u2 = math::atan((1.0 - F) * math::tan(p2.lat.to_radians()));
+        let l = (p2.lng - p1.lng).to_radians();
+
+        let (sin_u1, cos_u1) = math::sin_cos(u1);
+        let (sin_u2, cos_u2) = math::sin_cos(u2);
+
+        let mut lambda = l;
+        let mut cos_sq_alpha;
+        let mut sin_sigma;
+        let mut cos_sigma;
+        let mut sigma;
+        let mut cos2_sigma_m;
+
+        let mut iterations = 0;
+        loop {
+            let (sin_lambda, cos_lambda) = math::sin_cos(lambda);
+
+            sin_sigma = math::sqrt(
+                math::powi(cos_u2 * sin_lambda, 2)
+                    + math::powi(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda, 2),
+            );
+            if sin_sigma == 0.0 {
+                return 0.0; // 重合点
+            }
+
+            cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+            sigma = math::atan2(sin_sigma, cos_sigma);
+
+            let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+            cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+
+            cos2_sigma_m = if cos_sq_alpha != 0.0 {
+                cos_sigma - 2.0 * sin_u1 * sin_u2 / cos_sq_alpha
+            } else {
+                0.0 // 赤道线上时该项无定义
+            };
+
+            let c = F / 16.0 * cos_sq_alpha * (4.0 + F * (4.0 - 3.0 * cos_sq_alpha));
+            let lambda_prev = lambda;
+            lambda = l
+                + (1.0 - c)
+                    * F
+                    * sin_alpha
+                    * (sigma
+                        + c * sin_sigma
+                            * (cos2_sigma_m
+                                + c * cos_sigma * (-1.0 + 2.0 * cos2_sigma_m * cos2_sigma_m)));
+
+            iterations += 1;
+            if math::abs(lambda - lambda_prev) < CONVERGENCE_THRESHOLD || iterations >= MAX_ITERATIONS
+            {
+                break;
+            }
+        }
+
+        let u_sq = cos_sq_alpha * (A * A - B * B) / (B * B);
+        let big_a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+        let big_b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+        let delta_sigma = big_b
+            * sin_sigma
+            * (cos2_sigma_m
+                + big_b / 4.0
+                    * (cos_sigma * (-1.0 + 2.0 * cos2_sigma_m * cos2_sigma_m)
+                        - big_b / 6.0
+                            * cos2_sigma_m
+                            * (-3.0 + 4.0 * sin_sigma * sin_sigma)
+                            * (-3.0 + 4.0 * cos2_sigma_m * cos2_sigma_m)));
+
+        B * big_a * (sigma - delta_sigma)
+    }
+
+    // Vincenty 正解: 已知起点、方位角(度)与距离(米),求到达点的经纬度
+    pub fn vincenty_direct(start: Coordinate, bearing_deg: f64, distance_m: f64) -> Coordinate {
+        let alpha1 = bearing_deg.to_radians();
+        let (sin_alpha1, cos_alpha1) = math::sin_cos(alpha1);
+
+        let u1 = math::atan((1.0 - F) * math::tan(start.lat.to_radians()));
+        let (sin_u1, cos_u1) = math::sin_cos(u1);
+
+        let sigma1 = math::atan2(sin_u1, cos_u1 * cos_alpha1);
+        let sin_alpha = cos_u1 * sin_alpha1;
+        let cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+        let u_sq = cos_sq_alpha * (A * A - B * B) / (B * B);
+        let big_a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+        let big_b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+
+        let mut sigma = distance_m / (B * big_a);
+        let mut cos2_sigma_m;
+        let mut iterations = 0;
+        loop {
+            cos2_sigma_m = math::cos(2.0 * sigma1 + sigma);
+            let (sin_sigma, cos_sigma) = math::sin_cos(sigma);
+
+            let delta_sigma = big_b
+                * sin_sigma
+                * (cos2_sigma_m
+                    + big_b / 4.0
+                        * (cos_sigma * (-1.0 + 2.0 * cos2_sigma_m * cos2_sigma_m)
+                            - big_b / 6.0
+                                * cos2_sigma_m
+                                * (-3.0 + 4.0 * sin_sigma * sin_sigma)
+                                * (-3.0 + 4.0 * cos2_sigma_m * cos2_sigma_m)));
+
+            let sigma_prev = sigma;
+            sigma = distance_m / (B * big_a) + delta_sigma;
+
+            iterations += 1;
+            if math::abs(sigma - sigma_prev) < CONVERGENCE_THRESHOLD || iterations >= MAX_ITERATIONS {
+                break;
+            }
+        }
+
+        let (sin_sigma, cos_sigma) = math::sin_cos(sigma);
+        let lat2 = math::atan2(
+            sin_u1 * cos_sigma + cos_u1 * sin_sigma * cos_alpha1,
+            (1.0 - F)
+                * math::sqrt(
+                    sin_alpha * sin_alpha
+                        + math::powi(sin_u1 * sin_sigma - cos_u1 * cos_sigma * cos_alpha1, 2),
+                ),
+        );
+
+        let lambda = math::atan2(
+            sin_sigma * sin_alpha1,
+            cos_u1 * cos_sigma - sin_u1 * sin_sigma * cos_alpha1,
+        );
+        let c = F / 16.0 * cos_sq_alpha * (4.0 + F * (4.0 - 3.0 * cos_sq_alpha));
+        let l = lambda
+            - (1.0 - c)
+                * F
+                * sin_alpha
+                * (sigma
+                    + c * sin_sigma
+                        * (cos2_sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos2_sigma_m * cos2_sigma_m)));
+
+        let lng2 = start.lng + l.to_degrees();
+
+        Coordinate::new(lng2, lat2.to_degrees())
+    }
+}
+
+mod mercator {
+    use super::{math, Coordinate};
+
+    const EARTH_RADIUS: f64 = 6378137.0;
+
+    pub fn wgs84_to_web_mercator(coord: Coordinate) -> Coordinate {
+        let x = EARTH_RADIUS * coord.lng.to_radians();
+        let rad_lat = coord.lat.to_radians();
+        let y = EARTH_RADIUS * math::ln(math::tan(core::f64::consts::FRAC_PI_4 + rad_lat / 2.0));
+
+        Coordinate::new(x, y)
+    }
+
+    pub fn web_mercator_to_wgs84(coord: Coordinate) -> Coordinate {
+        let lng = (coord.lng / EARTH_RADIUS).to_degrees();
+        let lat = (2.0 * math::atan(math::exp(coord.lat / EARTH_RADIUS))
+            - core::f64::consts::FRAC_PI_2)
+            .to_degrees();
+
+        Coordinate::new(lng, lat)
+    }
+
+    // 百度 BD09 -> BD09MC 并非标准墨卡托投影,而是按纬度分段的多项式拟合,
+    // 系数来自百度地图 API 公开的分段表(按 |lat| 落入的区间选择对应系数行)。
+    const LLBAND: [f64; 6] = [75.0, 60.0, 45.0, 30.0, 15.0, 0.0];
+    const MCBAND: [f64; 6] = [12890594.86, 8362377.87, 5591021.0, 3481989.83, 1678043.12, 0.0];
+
+    const LL2MC: [[f64; 10]; 6] = [
+        [
+            -0.0015702102444,
+            111320.7020616939,
+            1704480524535203.0,
+            -10338987376042340.0,
+            26112667856603880.0,
+            -35149669176653700.0,
+            26595700718403920.0,
+            -10725012454188240.0,
+            1800819912950474.0,
+            82.5,
+        ],
+        [
+            0.0008277824516172526,
+            111320.7020463578,
+            647795574.6671607,
+            -4082003173.641316,
+            10774905663.51142,
+            -15171875531.51559,
+            12053065338.62167,
+            -5124939663.577472,
+            913311935.9512032,
+            67.5,
+        ],
+        [
+            0.00337398766765,
+            111320.7020202162,
+            4481351.045890365,
+            -23393751.19931662,
+            79682215.47186455,
+            -115964993.2797253,
+            97236711.15602145,
+            -43661946.33752821,
+            8477230.501135234,
+            52.5,
+        ],
+        [
+            0.00220636496208,
+            111320.7020209128,
+            51751.86112841131,
+            3796837.749470245,
+            992013.7397791013,
+            -1221952.21711287,
+            1340652.697009075,
+            -620943.6990984312,
+            144416.9293806241,
+            37.5,
+        ],
+        [
+            -0.0003441963504368392,
+            111320.7020576856,
+            278.2353980772752,
+            2485758.690035394,
+            6070.750963243378,
+            54821.18345352118,
+            9540.606633304236,
+            -2710.55326746645,
+            1405.483844121726,
+            22.5,
+        ],
+        [
+            -0.0003218135878613132,
+            111320.7020701615,
+            0.00369383431289,
+            823725.6402795718,
+            0.46104986909093,
+            2351.343141331292,
+            1.58060784298199,
+            8.77738589078284,
+            0.37238884252424,
+            7.45,
+        ],
+    ];
+
+    const MC2LL: [[f64; 10]; 6] = [
+        [
+            1.410526172116255e-8,
+            0.00000898305509648872,
+            -1.9939833816331,
+            200.9824383106796,
+            -187.2403703815547,
+            91.6087516669843,
+            -23.38765649603339,
+            2.57121317296198,
+            -0.03801003308653,
+            17337981.2,
+        ],
+        [
+            -7.435856389565537e-9,
+            0.000008983055097726239,
+            -0.78625201886289,
+            96.32687599759846,
+            -1.85204757529826,
+            -59.36935905485877,
+            47.40033549296737,
+            -16.50741931063887,
+            2.28786674699375,
+            10260144.86,
+        ],
+        [
+            -3.030883460898826e-8,
+            0.00000898305509983578,
+            0.30071316287616,
+            59.74293618442277,
+            7.357984074871,
+            -25.38371002664745,
+            13.45380521110908,
+            -3.29883767235584,
+            0.32710905363475,
+            6856817.37,
+        ],
+        [
+            -1.981981304930552e-8,
+            0.000008983055099779535,
+            0.03278182852591,
+            40.31678527705744,
+            0.65659298677277,
+            -4.44255534477492,
+            0.85341911805263,
+            0.12923347998204,
+            -0.04625736007561,
+            4482777.06,
+        ],
+        [
+            3.09191371068437e-9,
+            0.000008983055096812155,
+            0.00006995724062,
+            23.10934304144901,
+            -0.00023663490511,
+            -0.6321817810242,
+            -0.00663494467273,
+            0.03430082397953,
+            -0.00466043876332,
+            2555164.4,
+        ],
+        [
+            2.890871144776878e-9,
+            0.000008983055095805407,
+            -3.068298e-8,
+            7.47137025468032,
+            -0.00000353937994,
+            -0.02145144861037,
+            -0.00001234426596,
+            0.00010322952773,
+            -0.00000323890364,
+            826088.5,
+        ],
+    ];
+
+    // row[0]/row[1] 只用于 lng 的线性项;lat 走独立的六次多项式,系数是 row[2..=8],
+    // 自变量是 |lat| 除以 row[9] 后的归一化值 —— 两者不共享同一套系数,不能合并成一个 poly(row, x)
+    fn lat_poly(coeffs: &[f64; 10], c: f64) -> f64 {
+        coeffs[2]
+            + coeffs[3] * c
+            + coeffs[4] * c * c
+            + coeffs[5] * c * c * c
+            + coeffs[6] * c * c * c * c
+            + coeffs[7] * c * c * c * c * c
+            + coeffs[8] * c * c * c * c * c * c
+    }
+
+    pub fn bd09_to_bd09mc(coord: Coordinate) -> Coordinate {
+        let row = LLBAND
+            .iter()
+            .position(|&band| coord.lat.abs() >= band)
+            .and_then(|i| LL2MC.get(i))
+            .unwrap_or(&LL2MC[5]);
+
+        let lng = row[0] + row[1] * math::abs(coord.lng);
+        let c = math::abs(coord.lat) / row[9];
+        let lat = lat_poly(row, c);
+
+        let lng = if coord.lng < 0.0 { -lng } else { lng };
+        let lat = if coord.lat < 0.0 { -lat } else { lat };
+
+        Coordinate::new(lng, lat)
+    }
+
+    pub fn bd09mc_to_bd09(coord: Coordinate) -> Coordinate {
+        let row = MCBAND
+            .iter()
+            .position(|&band| coord.lat.abs() >= band)
+            .and_then(|i| MC2LL.get(i))
+            .unwrap_or(&MC2LL[5]);
+
+        let lng = row[0] + row[1] * math::abs(coord.lng);
+        let c = math::abs(coord.lat) / row[9];
+        let lat = lat_poly(row, c);
+
+        let lng = if coord.lng < 0.0 { -lng } else { lng };
+        let lat = if coord.lat < 0.0 { -lat } else { lat };
+
+        Coordinate::new(lng, lat)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -316,4 +1312,294 @@ mod tests {
             assert!((to.lat - expected.lat).abs() < EPSILON);
         }
     }
+
+    #[test]
+    fn test_wgs84_web_mercator() {
+        let from = Coordinate::new(116.407387, 39.904179);
+        let to = transform(from, CoordSystem::WGS84, CoordSystem::WebMercator);
+        assert!(to.is_ok());
+        let to = to.unwrap();
+        println!("{} {}", to.lng, to.lat);
+
+        let back = transform(to, CoordSystem::WebMercator, CoordSystem::WGS84);
+        assert!(back.is_ok());
+        let back = back.unwrap();
+        assert!((back.lng - from.lng).abs() < EPSILON);
+        assert!((back.lat - from.lat).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_bd09_bd09mc() {
+        let from = Coordinate::new(116.413772, 39.910501);
+        let to = transform(from, CoordSystem::BD09, CoordSystem::BD09MC);
+        assert!(to.is_ok());
+        let to = to.unwrap();
+        println!("{} {}", to.lng, to.lat);
+
+        let back = transform(to, CoordSystem::BD09MC, CoordSystem::BD09);
+        assert!(back.is_ok());
+        let back = back.unwrap();
+        assert!((back.lng - from.lng).abs() < EPSILON);
+        assert!((back.lat - from.lat).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_distance() {
+        // 北京天安门 到 上海人民广场,约 1067 公里
+        let beijing = Coordinate::new(116.397451, 39.909187);
+        let shanghai = Coordinate::new(121.469170, 31.233414);
+
+        let d = distance(beijing, shanghai, CoordSystem::WGS84);
+        assert!(d.is_ok());
+        let d = d.unwrap();
+        assert!((d - 1_067_000.0).abs() < 10_000.0);
+
+        let same = distance(beijing, beijing, CoordSystem::WGS84);
+        assert_eq!(same, Ok(0.0));
+    }
+
+    #[test]
+    fn test_destination() {
+        let start = Coordinate::new(116.397451, 39.909187);
+        let dest = start.destination(90.0, 10_000.0, CoordSystem::WGS84);
+        assert!(dest.is_ok());
+        let dest = dest.unwrap();
+
+        let d = distance(start, dest, CoordSystem::WGS84).unwrap();
+        assert!((d - 10_000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_parse_decimal() {
+        let coord: Coordinate = "39.9042, 116.4074".parse().unwrap();
+        assert!((coord.lat - 39.9042).abs() < EPSILON);
+        assert!((coord.lng - 116.4074).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_parse_dms() {
+        let coord: Coordinate = "39°54′15″N 116°24′26″E".parse().unwrap();
+        assert!((coord.lat - (39.0 + 54.0 / 60.0 + 15.0 / 3600.0)).abs() < EPSILON);
+        assert!((coord.lng - (116.0 + 24.0 / 60.0 + 26.0 / 3600.0)).abs() < EPSILON);
+
+        let coord2: Coordinate = "39 54 15 N 116 24 26 E".parse().unwrap();
+        assert_eq!(coord, coord2);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let result: Result<Coordinate, _> = "not a coordinate".parse();
+        assert_eq!(result, Err(ConvertError::ParseError));
+
+        let result: Result<Coordinate, _> = "95.0, 116.4074".parse();
+        assert_eq!(result, Err(ConvertError::ParseError));
+    }
+
+    #[test]
+    fn test_ecef_round_trip() {
+        let coord = Coordinate::new(116.397451, 39.909187);
+        let height = 50.0;
+
+        let (x, y, z) = geodetic_to_ecef(coord, height, Ellipsoid::WGS84);
+        let (back, back_height) = ecef_to_geodetic(x, y, z, Ellipsoid::WGS84);
+
+        assert!((back.lng - coord.lng).abs() < EPSILON);
+        assert!((back.lat - coord.lat).abs() < EPSILON);
+        assert!((back_height - height).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_transform_datum_identity() {
+        let coord = Coordinate::new(116.397451, 39.909187);
+        let identity = Helmert {
+            dx: 0.0,
+            dy: 0.0,
+            dz: 0.0,
+            rx: 0.0,
+            ry: 0.0,
+            rz: 0.0,
+            scale_ppm: 0.0,
+        };
+
+        let result = transform_datum(coord, 0.0, Ellipsoid::WGS84, Ellipsoid::WGS84, identity);
+
+        assert!((result.lng - coord.lng).abs() < EPSILON);
+        assert!((result.lat - coord.lat).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_transform_custom_datum_round_trip() {
+        // 随手编的一个自定义基准面(非真实坐标系),只用来验证 CoordSystem::Custom 能
+        // 经由 WGS84 往返,不代表任何实际生产环境中的基准面
+        let custom = CoordSystem::Custom(CustomDatum {
+            ellipsoid: Ellipsoid::WGS84,
+            to_wgs84: Helmert {
+                dx: 10.0,
+                dy: -5.0,
+                dz: 3.0,
+                rx: 0.02,
+                ry: -0.01,
+                rz: 0.03,
+                scale_ppm: 1.5,
+            },
+        });
+
+        let coord = Coordinate::new(116.397451, 39.909187);
+        let wgs84 = transform(coord, custom, CoordSystem::WGS84).unwrap();
+        assert!((wgs84.lng - coord.lng).abs() > EPSILON || (wgs84.lat - coord.lat).abs() > EPSILON);
+
+        let back = transform(wgs84, CoordSystem::WGS84, custom).unwrap();
+        assert!((back.lng - coord.lng).abs() < 1e-6);
+        assert!((back.lat - coord.lat).abs() < 1e-6);
+
+        // 与现有 GCJ02 步骤组合使用
+        let gcj02 = transform(coord, custom, CoordSystem::GCJ02).unwrap();
+        let roundtrip = transform(gcj02, CoordSystem::GCJ02, custom).unwrap();
+        assert!((roundtrip.lng - coord.lng).abs() < 1e-5);
+        assert!((roundtrip.lat - coord.lat).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_transform_slice_custom_datum() {
+        let custom = CoordSystem::Custom(CustomDatum {
+            ellipsoid: Ellipsoid::WGS84,
+            to_wgs84: Helmert {
+                dx: 10.0,
+                dy: -5.0,
+                dz: 3.0,
+                rx: 0.0,
+                ry: 0.0,
+                rz: 0.0,
+                scale_ppm: 0.0,
+            },
+        });
+
+        let coords = vec![
+            Coordinate::new(114.304569, 30.593354),
+            Coordinate::new(116.407387, 39.904179),
+        ];
+
+        let results = transform_slice(&coords, CoordSystem::WGS84, custom);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.is_ok()));
+    }
+
+    #[test]
+    fn test_transform_slice() {
+        let coords = vec![
+            Coordinate::new(114.304569, 30.593354),
+            Coordinate::new(116.407387, 39.904179),
+        ];
+
+        let results = transform_slice(&coords, CoordSystem::WGS84, CoordSystem::GCJ02);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.is_ok()));
+
+        let mut mutable = coords.clone();
+        assert!(transform_slice_mut(&mut mutable, CoordSystem::WGS84, CoordSystem::GCJ02).is_ok());
+        for (slice_result, mutated) in results.iter().zip(mutable.iter()) {
+            let expected = slice_result.as_ref().unwrap();
+            assert!((expected.lng - mutated.lng).abs() < EPSILON);
+            assert!((expected.lat - mutated.lat).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_transform_slice_mut_surfaces_failing_index() {
+        let wuhan = Coordinate::new(114.304569, 30.593354);
+        let outside_china = Coordinate::new(2.349014, 48.864716); // 巴黎,触发 OutOfChina
+        let mut coords = vec![wuhan, outside_china, wuhan];
+
+        let err = transform_slice_mut(&mut coords, CoordSystem::GCJ02, CoordSystem::WGS84)
+            .expect_err("第二个坐标不在中国范围内,应当报错");
+        assert_eq!(err, (1, ConvertError::OutOfChina));
+
+        // 失败下标之前的元素已经被就地转换,之后的元素保持原值,调用方可以据此恢复
+        assert_ne!(coords[0], wuhan);
+        assert_eq!(coords[2], wuhan);
+    }
+
+    #[test]
+    fn test_transform_geometry() {
+        let polygon = Geometry::Polygon(vec![vec![
+            Coordinate::new(114.304569, 30.593354),
+            Coordinate::new(116.407387, 39.904179),
+            Coordinate::new(114.304569, 30.593354),
+        ]]);
+
+        let result = transform_geometry(&polygon, CoordSystem::WGS84, CoordSystem::GCJ02);
+        assert!(result.is_ok());
+        if let Ok(Geometry::Polygon(rings)) = result {
+            assert_eq!(rings.len(), 1);
+            assert_eq!(rings[0].len(), 3);
+        } else {
+            panic!("expected a polygon");
+        }
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(Coordinate::try_new(116.4074, 39.9042).is_ok());
+        assert_eq!(
+            Coordinate::try_new(200.0, 39.9042),
+            Err(ConvertError::OutOfRange)
+        );
+        assert_eq!(
+            Coordinate::try_new(116.4074, 95.0),
+            Err(ConvertError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_fixed_round_trip() {
+        let coord = Coordinate::new(116.4074321, 39.9042123);
+        let fixed = coord.to_fixed().unwrap();
+        assert!(fixed.is_valid());
+
+        let back = Coordinate::from_fixed(fixed).unwrap();
+        assert!((back.lng - coord.lng).abs() < 1e-7);
+        assert!((back.lat - coord.lat).abs() < 1e-7);
+
+        let invalid = FixedCoordinate {
+            lng: FixedCoordinate::INVALID,
+            lat: 0,
+        };
+        assert!(!invalid.is_valid());
+        assert_eq!(Coordinate::from_fixed(invalid), None);
+    }
+
+    #[test]
+    fn test_to_fixed_rejects_out_of_range() {
+        // 越界坐标缩放后会饱和到 i32::MIN,与 FixedCoordinate::INVALID 撞车,
+        // 必须在缩放前拒绝,而不是悄悄生成一个看似"无效坐标"的哨兵值
+        let out_of_range = Coordinate::new(-215.0, 10.0);
+        assert_eq!(out_of_range.to_fixed(), Err(ConvertError::OutOfRange));
+    }
+
+    use proptest::prelude::*;
+
+    proptest! {
+        // 中国境内范围内,WGS84 -> GCJ02 -> WGS84 应当在误差范围内往返一致
+        #[test]
+        fn prop_wgs84_gcj02_round_trip(lng in 73.0..135.0f64, lat in 4.0..53.0f64) {
+            let wgs84 = Coordinate::new(lng, lat);
+            let gcj02 = transform(wgs84, CoordSystem::WGS84, CoordSystem::GCJ02).unwrap();
+            let back = transform(gcj02, CoordSystem::GCJ02, CoordSystem::WGS84).unwrap();
+
+            prop_assert!((back.lng - wgs84.lng).abs() < 1e-5);
+            prop_assert!((back.lat - wgs84.lat).abs() < 1e-5);
+        }
+
+        // GCJ02 -> BD09 -> GCJ02 并非精确解析互逆(偏移量是经验拟合,非严格可逆函数),
+        // 实测在 [73,135]x[4,53] 范围内往返误差可达约 2e-6,容差需要覆盖这个量级
+        #[test]
+        fn prop_gcj02_bd09_round_trip(lng in 73.0..135.0f64, lat in 4.0..53.0f64) {
+            let gcj02 = Coordinate::new(lng, lat);
+            let bd09 = transform(gcj02, CoordSystem::GCJ02, CoordSystem::BD09).unwrap();
+            let back = transform(bd09, CoordSystem::BD09, CoordSystem::GCJ02).unwrap();
+
+            prop_assert!((back.lng - gcj02.lng).abs() < 1e-5);
+            prop_assert!((back.lat - gcj02.lat).abs() < 1e-5);
+        }
+    }
 }